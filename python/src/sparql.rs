@@ -9,7 +9,9 @@ use pyo3::exceptions::{
 };
 
 use pyo3::prelude::*;
+use std::rc::Rc;
 use std::vec::IntoIter;
+use std::{iter, mem};
 
 pub fn parse_query(
     query: &str,
@@ -192,6 +194,44 @@ impl PyQuerySolutions {
             .collect()
     }
 
+    /// Serializes the remaining solutions using a SPARQL query results format.
+    ///
+    /// The solutions are consumed: the iterator is exhausted after this call.
+    ///
+    /// It currently supports the following formats:
+    ///
+    /// * `XML <https://www.w3.org/TR/rdf-sparql-XMLres/>`_ (``application/sparql-results+xml``)
+    /// * `JSON <https://www.w3.org/TR/sparql11-results-json/>`_ (``application/sparql-results+json``)
+    /// * `CSV <https://www.w3.org/TR/sparql11-results-csv-tsv/>`_ (``text/csv``)
+    /// * `TSV <https://www.w3.org/TR/sparql11-results-csv-tsv/>`_ (``text/tab-separated-values``)
+    ///
+    /// :param mime_type: the MIME type of the results serialization. By default, SPARQL JSON results are produced.
+    /// :type mime_type: str, optional
+    /// :return: the serialized results.
+    /// :rtype: str
+    /// :raises ValueError: if the MIME type is not supported.
+    /// :raises IOError: if an I/O error happens while evaluating the solutions.
+    ///
+    /// >>> store = Store()
+    /// >>> store.add(Quad(NamedNode('http://example.com'), NamedNode('http://example.com/p'), Literal('1')))
+    /// >>> store.query('SELECT ?s WHERE { ?s ?p ?o }').serialize()
+    /// '{"head":{"vars":["s"]},"results":{"bindings":[{"s":{"type":"uri","value":"http://example.com"}}]}}'
+    #[pyo3(signature = (mime_type = "application/sparql-results+json"))]
+    fn serialize(&mut self, mime_type: &str) -> PyResult<String> {
+        let format = QueryResultsFormat::from_media_type(mime_type).ok_or_else(|| {
+            PyValueError::new_err(format!("Not supported MIME type: {mime_type}"))
+        })?;
+        let variables = Rc::new(self.inner.variables().to_vec());
+        let solutions = mem::replace(
+            &mut self.inner,
+            QuerySolutionIter::new(variables, iter::empty()),
+        );
+        let mut buffer = Vec::new();
+        allow_threads_unsafe(|| QueryResults::Solutions(solutions).write(&mut buffer, format))
+            .map_err(map_evaluation_error)?;
+        String::from_utf8(buffer).map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    }
+
     fn __iter__(slf: PyRef<'_, Self>) -> PyRef<Self> {
         slf
     }
@@ -144,6 +144,20 @@ class TestStore(unittest.TestCase):
         self.assertEqual(s, foo)
         self.assertEqual(o, baz)
 
+    def test_select_query_serialize(self) -> None:
+        store = Store()
+        store.add(Quad(foo, bar, baz))
+        solutions: Any = store.query("SELECT ?s WHERE { ?s ?p ?o }")
+        self.assertEqual(
+            solutions.serialize(),
+            '{"head":{"vars":["s"]},"results":{"bindings":[{"s":{"type":"uri","value":"http://foo"}}]}}',
+        )
+        self.assertEqual(list(solutions), [])
+        solutions = store.query("SELECT ?s WHERE { ?s ?p ?o }")
+        self.assertEqual(solutions.serialize("text/csv"), "s\r\nhttp://foo\r\n")
+        with self.assertRaises(ValueError):
+            store.query("SELECT ?s WHERE { ?s ?p ?o }").serialize("text/foo")
+
     def test_select_query_union_default_graph(self) -> None:
         store = Store()
         store.add(Quad(foo, bar, baz, graph))
@@ -221,6 +235,24 @@ class TestStore(unittest.TestCase):
         )
         self.assertEqual(len(list(results)), 1)
 
+    def test_select_query_star(self) -> None:
+        store = Store()
+        store.add(Quad(triple, bar, baz))
+        solutions: Any = store.query("SELECT ?s WHERE { ?s ?p ?o }")
+        solution = next(solutions)
+        self.assertIsInstance(solution["s"], Triple)
+        self.assertEqual(solution["s"], triple)
+        self.assertEqual(solution["s"].subject, foo)
+
+    def test_construct_query_star(self) -> None:
+        store = Store()
+        store.add(Quad(foo, bar, Triple(triple, bar, baz)))
+        results: Any = store.query("CONSTRUCT { ?o ?p ?s } WHERE { ?s ?p ?o }")
+        self.assertEqual(
+            set(results),
+            {Triple(Triple(triple, bar, baz), bar, foo)},
+        )
+
     def test_load_ntriples_to_default_graph(self) -> None:
         store = Store()
         store.load(
@@ -264,6 +296,43 @@ class TestStore(unittest.TestCase):
         )
         self.assertEqual(set(store), {Quad(foo, bar, baz, graph)})
 
+    def test_bulk_load_nquads(self) -> None:
+        store = Store()
+        store.bulk_load(
+            BytesIO(
+                b"<http://foo> <http://bar> <http://baz> <http://graph>.\n"
+                b"<http://foo> <http://bar> <http://baz> ."
+            ),
+            mime_type="application/n-quads",
+        )
+        self.assertEqual(
+            set(store),
+            {Quad(foo, bar, baz, graph), Quad(foo, bar, baz, DefaultGraph())},
+        )
+
+    def test_bulk_load_trig_with_base_iri(self) -> None:
+        store = Store()
+        store.bulk_load(
+            BytesIO(b"<http://graph> { <http://foo> <http://bar> <> . }"),
+            mime_type="application/trig",
+            base_iri="http://baz",
+        )
+        self.assertEqual(set(store), {Quad(foo, bar, baz, graph)})
+
+    def test_bulk_load_with_syntax_error(self) -> None:
+        with self.assertRaises(SyntaxError):
+            Store().bulk_load(
+                BytesIO(b"<http://foo> <http://bar> ."), mime_type="application/n-quads"
+            )
+
+    def test_bulk_load_to_named_graph_with_dataset_format(self) -> None:
+        with self.assertRaises(ValueError):
+            Store().bulk_load(
+                BytesIO(b"<http://foo> <http://bar> <http://baz> ."),
+                mime_type="application/n-quads",
+                to_graph=graph,
+            )
+
     def test_load_file(self) -> None:
         with NamedTemporaryFile(delete=False) as fp:
             file_name = fp.name
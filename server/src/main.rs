@@ -1,7 +1,8 @@
 #![allow(clippy::print_stderr, clippy::cast_precision_loss, clippy::use_debug)]
 use anyhow::{anyhow, bail, Context, Error};
 use clap::{Parser, Subcommand};
-use flate2::read::MultiGzDecoder;
+use flate2::read::{GzEncoder, MultiGzDecoder, ZlibEncoder};
+use flate2::Compression;
 use oxhttp::model::{Body, HeaderName, HeaderValue, Method, Request, Response, Status};
 use oxhttp::Server;
 use oxigraph::io::{DatasetFormat, DatasetSerializer, GraphFormat, GraphSerializer};
@@ -21,7 +22,7 @@ use std::cmp::{max, min};
 use std::env;
 use std::ffi::OsStr;
 use std::fs::File;
-use std::io::{self, stdin, stdout, BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{self, stdin, stdout, BufRead, BufReader, BufWriter, Cursor, Read, Write};
 #[cfg(target_os = "linux")]
 use std::os::unix::net::UnixDatagram;
 use std::path::{Path, PathBuf};
@@ -33,6 +34,7 @@ use std::{fmt, fs, str};
 use url::form_urlencoded;
 
 const MAX_SPARQL_BODY_SIZE: u64 = 0x0010_0000;
+const MIN_COMPRESSED_BODY_SIZE: u64 = 0x400;
 const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
 const HTML_ROOT_PAGE: &str = include_str!("../templates/query.html");
 const LOGO: &str = include_str!("../logo.svg");
@@ -855,6 +857,18 @@ fn handle_request(
             .with_header(HeaderName::CONTENT_TYPE, "image/svg+xml")
             .unwrap()
             .with_body(LOGO)),
+        ("/zk/health", "GET") => {
+            // Reads at most one quad: it is enough to check that the storage answers
+            if let Some(quad) = store.quads_for_pattern(None, None, None, None).next() {
+                quad.map_err(|e| {
+                    (
+                        Status::SERVICE_UNAVAILABLE,
+                        format!("The store is not queryable: {e}"),
+                    )
+                })?;
+            }
+            Ok(Response::builder(Status::OK).build())
+        }
         ("/query", "GET") => {
             configure_and_evaluate_sparql_query(&store, &[url_query(request)], None, request)
         }
@@ -954,6 +968,7 @@ fn handle_request(
                         })
                     },
                     format.media_type(),
+                    request,
                 )
             } else {
                 let format = dataset_content_negotiation(request)?;
@@ -974,6 +989,7 @@ fn handle_request(
                         })
                     },
                     format.media_type(),
+                    request,
                 )
             }
         }
@@ -1228,6 +1244,7 @@ fn evaluate_sparql_query(
                     })
                 },
                 format.media_type(),
+                request,
             )
         }
         QueryResults::Boolean(result) => {
@@ -1260,6 +1277,7 @@ fn evaluate_sparql_query(
                     })
                 },
                 format.media_type(),
+                request,
             )
         }
     }
@@ -1520,6 +1538,61 @@ fn content_negotiation<F>(
     parse(result).ok_or_else(|| internal_server_error("Unknown media type"))
 }
 
+#[derive(Clone, Copy)]
+enum ContentEncoding {
+    Gzip,
+    Deflate,
+}
+
+/// Picks the preferred compression from the Accept-Encoding header
+///
+/// Returns `None` if the body should not be compressed. Invalid headers are ignored because the identity encoding is always acceptable.
+fn content_encoding_negotiation(request: &Request) -> Option<ContentEncoding> {
+    let header = request
+        .header(&HeaderName::ACCEPT_ENCODING)?
+        .to_str()
+        .ok()?;
+    let codings = header
+        .split(',')
+        .map(|possible| {
+            let (possible, parameters) = possible.split_once(';').unwrap_or((possible, ""));
+            let mut score = 1.;
+            for parameter in parameters.split(';') {
+                if let Some(s) = parameter.trim().strip_prefix("q=") {
+                    score = f32::from_str(s.trim()).unwrap_or(0.);
+                }
+            }
+            (possible.trim().to_ascii_lowercase(), score)
+        })
+        .collect::<Vec<_>>();
+    let is_listed = |name: &str| codings.iter().any(|(coding, _)| coding == name);
+    let mut result = None;
+    let mut result_score = 0_f32;
+
+    for (coding, score) in &codings {
+        if *score <= result_score {
+            continue;
+        }
+        result = Some(match coding.as_str() {
+            "gzip" | "x-gzip" => Some(ContentEncoding::Gzip),
+            "deflate" => Some(ContentEncoding::Deflate),
+            "identity" => None,
+            // "*" only matches the codings not explicitly listed in the header
+            "*" => match (
+                is_listed("gzip") || is_listed("x-gzip"),
+                is_listed("deflate"),
+            ) {
+                (false, _) => Some(ContentEncoding::Gzip),
+                (true, false) => Some(ContentEncoding::Deflate),
+                (true, true) => continue,
+            },
+            _ => continue,
+        });
+        result_score = *score;
+    }
+    result.flatten()
+}
+
 fn content_type(request: &Request) -> Option<String> {
     let value = request.header(&HeaderName::CONTENT_TYPE)?.to_str().ok()?;
     Some(
@@ -1644,21 +1717,55 @@ impl<O: 'static, U: (Fn(O) -> io::Result<Option<O>>) + 'static> ReadForWrite<O,
         initial_state_builder: impl FnOnce(ReadForWriteWriter) -> io::Result<O>,
         add_more_data: U,
         content_type: &'static str,
+        request: &Request,
     ) -> Result<Response, HttpError> {
         let buffer = Rc::new(RefCell::new(Vec::new()));
         let state = initial_state_builder(ReadForWriteWriter {
             buffer: Rc::clone(&buffer),
         })
         .map_err(internal_server_error)?;
-        Ok(Response::builder(Status::OK)
+        let mut body = Self {
+            buffer,
+            position: 0,
+            add_more_data,
+            state: Some(state),
+        };
+        let response = Response::builder(Status::OK)
             .with_header(HeaderName::CONTENT_TYPE, content_type)
             .unwrap()
-            .with_body(Body::from_read(Self {
-                buffer,
-                position: 0,
-                add_more_data,
-                state: Some(state),
-            })))
+            .with_header(HeaderName::VARY, "Accept-Encoding")
+            .unwrap();
+        let encoding = if let Some(encoding) = content_encoding_negotiation(request) {
+            encoding
+        } else {
+            return Ok(response.with_body(Body::from_read(body)));
+        };
+        // We look at the beginning of the body to avoid compressing small responses
+        let mut start = Vec::new();
+        (&mut body)
+            .take(MIN_COMPRESSED_BODY_SIZE)
+            .read_to_end(&mut start)
+            .map_err(internal_server_error)?;
+        if (start.len() as u64) < MIN_COMPRESSED_BODY_SIZE {
+            return Ok(response.with_body(start));
+        }
+        let body = Cursor::new(start).chain(body);
+        Ok(match encoding {
+            ContentEncoding::Gzip => response
+                .with_header(HeaderName::CONTENT_ENCODING, "gzip")
+                .unwrap()
+                .with_body(Body::from_read(GzEncoder::new(
+                    body,
+                    Compression::default(),
+                ))),
+            ContentEncoding::Deflate => response
+                .with_header(HeaderName::CONTENT_ENCODING, "deflate")
+                .unwrap()
+                .with_body(Body::from_read(ZlibEncoder::new(
+                    body,
+                    Compression::default(),
+                ))),
+        })
     }
 }
 
@@ -1723,6 +1830,7 @@ mod tests {
     use anyhow::Result;
     use assert_cmd::Command;
     use assert_fs::{prelude::*, NamedTempFile, TempDir};
+    use flate2::read::ZlibDecoder;
     use flate2::write::GzEncoder;
     use flate2::Compression;
     use oxhttp::model::Method;
@@ -2118,6 +2226,20 @@ mod tests {
         )
     }
 
+    #[test]
+    fn get_zk_health() -> Result<()> {
+        let server = ServerTest::new()?;
+        let request = Request::builder(Method::GET, "http://localhost/zk/health".parse()?).build();
+        server.test_status(request, Status::OK)?;
+
+        let request = Request::builder(Method::POST, "http://localhost/store".parse()?)
+            .with_header(HeaderName::CONTENT_TYPE, "application/trig")?
+            .with_body("<http://example.com> <http://example.com> <http://example.com> .");
+        server.test_status(request, Status::NO_CONTENT)?;
+        let request = Request::builder(Method::GET, "http://localhost/zk/health".parse()?).build();
+        server.test_status(request, Status::OK)
+    }
+
     #[test]
     fn post_dataset_file() -> Result<()> {
         let request = Request::builder(Method::POST, "http://localhost/store".parse()?)
@@ -2164,6 +2286,85 @@ mod tests {
         )
     }
 
+    #[test]
+    fn get_query_compressed() -> Result<()> {
+        let server = ServerTest::new()?;
+
+        let data = (0..100)
+            .map(|i| format!("<http://example.com/{i}> <http://example.com/p> \"{i}\" .\n"))
+            .collect::<String>();
+        let request = Request::builder(Method::PUT, "http://localhost/store?default".parse()?)
+            .with_header(HeaderName::CONTENT_TYPE, "application/n-triples")?
+            .with_body(data.into_bytes());
+        server.test_status(request, Status::NO_CONTENT)?;
+
+        let query_request = |query: &str, accept_encoding: Option<&str>| -> Result<Request> {
+            let mut request = Request::builder(
+                Method::GET,
+                format!("http://localhost/query?query={query}").parse()?,
+            )
+            .with_header(HeaderName::ACCEPT, "application/sparql-results+json")?;
+            if let Some(accept_encoding) = accept_encoding {
+                request = request.with_header(HeaderName::ACCEPT_ENCODING, accept_encoding)?;
+            }
+            Ok(request.build())
+        };
+        let header = |response: &Response, name: &HeaderName| {
+            response
+                .header(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let content_encoding = |response: &Response| {
+            assert_eq!(
+                header(response, &HeaderName::VARY).as_deref(),
+                Some("Accept-Encoding")
+            );
+            header(response, &HeaderName::CONTENT_ENCODING)
+        };
+        let query = "SELECT%20?s%20?p%20?o%20WHERE%20{%20?s%20?p%20?o%20}";
+
+        let mut response = server.exec(query_request(query, None)?);
+        assert_eq!(response.status(), Status::OK);
+        assert_eq!(content_encoding(&response), None);
+        let mut expected = Vec::new();
+        response.body_mut().read_to_end(&mut expected)?;
+
+        let mut response = server.exec(query_request(query, Some("deflate;q=0.5, gzip"))?);
+        assert_eq!(response.status(), Status::OK);
+        assert_eq!(content_encoding(&response).as_deref(), Some("gzip"));
+        let mut actual = Vec::new();
+        MultiGzDecoder::new(response.body_mut()).read_to_end(&mut actual)?;
+        assert_eq!(actual, expected);
+
+        for accept_encoding in ["deflate", "gzip;q=0, *"] {
+            let mut response = server.exec(query_request(query, Some(accept_encoding))?);
+            assert_eq!(response.status(), Status::OK);
+            assert_eq!(content_encoding(&response).as_deref(), Some("deflate"));
+            let mut actual = Vec::new();
+            ZlibDecoder::new(response.body_mut()).read_to_end(&mut actual)?;
+            assert_eq!(actual, expected);
+        }
+
+        for accept_encoding in ["br, gzip;q=0", "identity, gzip;q=0.5"] {
+            let mut response = server.exec(query_request(query, Some(accept_encoding))?);
+            assert_eq!(response.status(), Status::OK);
+            assert_eq!(content_encoding(&response), None);
+            let mut actual = Vec::new();
+            response.body_mut().read_to_end(&mut actual)?;
+            assert_eq!(actual, expected);
+        }
+
+        // Small responses are not compressed
+        let response = server.exec(query_request(
+            "SELECT%20?s%20WHERE%20{%20?s%20?p%20?o%20}%20LIMIT%201",
+            Some("gzip"),
+        )?);
+        assert_eq!(response.status(), Status::OK);
+        assert_eq!(content_encoding(&response), None);
+        Ok(())
+    }
+
     #[test]
     fn get_query_accept_star() -> Result<()> {
         let request = Request::builder(